@@ -2,13 +2,106 @@ use crate::models::{AnalysisRequest, AnalysisResult, HealthResponse, SecurityFin
 use anyhow::{Context, Result};
 use futures::StreamExt;
 use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use std::time::Duration;
 use log::{debug};
 
+/// A recorded HTTP + SSE interaction, keyed by a fingerprint of the
+/// request that produced it. Written under `SKYNET_RECORD_DIR` and read
+/// back under `SKYNET_REPLAY_DIR` so the streaming parser in
+/// [`ApiClient::analyze_stream`] can be exercised without a live gateway.
+///
+/// `chunks` holds the raw text of each network read exactly as it arrived,
+/// so replay feeds it through [`SseLineBuffer::feed`] the same way live
+/// traffic does — including any partial lines split across chunk
+/// boundaries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Recording {
+    request_fingerprint: String,
+    status: u16,
+    chunks: Vec<String>,
+}
+
+/// Accumulates bytes across chunk boundaries and emits complete
+/// `event:`/`data:` pairs as they become available. Shared by the live
+/// network path and replay so both exercise identical parsing logic.
+struct SseLineBuffer {
+    buffer: String,
+    event_type: Option<String>,
+}
+
+impl SseLineBuffer {
+    fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            event_type: None,
+        }
+    }
+
+    /// Feed a chunk of text, invoking `on_event` for each complete
+    /// `(event, data)` pair found. Handles partial lines split across
+    /// chunk boundaries by buffering the remainder for the next call.
+    fn feed(&mut self, text: &str, mut on_event: impl FnMut(&str, &str)) {
+        self.buffer.push_str(text);
+
+        while let Some(newline_pos) = self.buffer.find('\n') {
+            let line = self.buffer[..newline_pos].to_string();
+            self.buffer = self.buffer[newline_pos + 1..].to_string();
+
+            let line = line.trim();
+
+            if line.is_empty() {
+                self.event_type = None;
+                continue;
+            }
+
+            if let Some(event) = line.strip_prefix("event: ") {
+                self.event_type = Some(event.to_string());
+            } else if let Some(data) = line.strip_prefix("data: ") {
+                if let Some(event_type) = self.event_type.as_deref() {
+                    on_event(event_type, data);
+                }
+            }
+        }
+    }
+}
+
+/// Feed one chunk of raw SSE text through `parser`, dispatching each
+/// complete event to `on_finding` (for `finding` events) or bailing out
+/// (for `error` events). Shared by the live network path and replay so
+/// both exercise identical parsing logic.
+fn process_sse_chunk(
+    parser: &mut SseLineBuffer,
+    text: &str,
+    on_finding: &mut impl FnMut(SecurityFinding),
+) -> Result<()> {
+    let mut error_data: Option<String> = None;
+
+    parser.feed(text, |event_type, data| match event_type {
+        "finding" => match serde_json::from_str::<SecurityFinding>(data) {
+            Ok(finding) => on_finding(finding),
+            Err(e) => debug!("Warning: Failed to parse finding: {e} (data: {data})"),
+        },
+        "error" => error_data = Some(data.to_string()),
+        _ => {}
+    });
+
+    if let Some(data) = error_data {
+        anyhow::bail!("Server error: {data}");
+    }
+
+    Ok(())
+}
+
 pub struct ApiClient {
     base_url: String,
     client: Client,
     api_key: Option<String>,
+    record_dir: Option<PathBuf>,
+    replay_dir: Option<PathBuf>,
 }
 
 impl ApiClient {
@@ -37,13 +130,33 @@ impl ApiClient {
             .build()
             .context("Failed to create HTTP client")?;
 
+        // Under SKYNET_RECORD_DIR each streaming call still goes to the
+        // network and its raw chunks are written to that directory; under
+        // SKYNET_REPLAY_DIR they are read back instead of hitting the
+        // network. Used by the test suite to exercise `analyze_stream`
+        // deterministically.
+        let record_dir = std::env::var("SKYNET_RECORD_DIR").ok().map(PathBuf::from);
+        let replay_dir = std::env::var("SKYNET_REPLAY_DIR").ok().map(PathBuf::from);
+
         Ok(Self {
             base_url: base_url.to_string(),
             client,
             api_key,
+            record_dir,
+            replay_dir,
         })
     }
 
+    /// Derive a stable fingerprint for a request so recordings and replays
+    /// agree on which file corresponds to which call.
+    fn fingerprint_request(method: &str, url: &str, body: &str) -> String {
+        let mut hasher = DefaultHasher::new();
+        method.hash(&mut hasher);
+        url.hash(&mut hasher);
+        body.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
     pub async fn analyze(&self, request: AnalysisRequest) -> Result<Vec<AnalysisResult>> {
         let url = format!("{}/api/analyze", self.base_url);
 
@@ -103,6 +216,12 @@ impl ApiClient {
         F: FnMut(SecurityFinding),
     {
         let url = format!("{}/api/analyze/stream", self.base_url);
+        let body = serde_json::to_string(&request).context("Failed to serialize request")?;
+        let fingerprint = Self::fingerprint_request("POST", &url, &body);
+
+        if let Some(replay_dir) = &self.replay_dir {
+            return Self::replay_stream(replay_dir, &fingerprint, on_finding);
+        }
 
         let mut req = self.client.post(&url).json(&request);
 
@@ -116,54 +235,202 @@ impl ApiClient {
             .await
             .context("Streaming analysis request failed (timeout or connection issue)")?;
 
-        if !response.status().is_success() {
-            let status = response.status();
+        let status = response.status();
+        if !status.is_success() {
             anyhow::bail!("API request failed with status {status}");
         }
 
         let mut stream = response.bytes_stream();
-        let mut buffer = String::new();
-        let mut event_type: Option<String> = None;
+        let mut parser = SseLineBuffer::new();
+        let mut recorded_chunks: Vec<String> = Vec::new();
 
         while let Some(chunk) = stream.next().await {
             let chunk = chunk.map_err(|e| anyhow::anyhow!("Stream read error: {e}"))?;
-            let text = String::from_utf8_lossy(&chunk);
-            buffer.push_str(&text);
+            let text = String::from_utf8_lossy(&chunk).into_owned();
 
-            // Process complete lines
-            while let Some(newline_pos) = buffer.find('\n') {
-                let line = buffer[..newline_pos].to_string();
-                buffer = buffer[newline_pos + 1..].to_string();
+            if self.record_dir.is_some() {
+                recorded_chunks.push(text.clone());
+            }
 
-                let line = line.trim();
+            process_sse_chunk(&mut parser, &text, &mut on_finding)?;
+        }
 
-                if line.is_empty() {
-                    event_type = None;
-                    continue;
-                }
+        if let Some(record_dir) = &self.record_dir {
+            let recording = Recording {
+                request_fingerprint: fingerprint.clone(),
+                status: status.as_u16(),
+                chunks: recorded_chunks,
+            };
+            std::fs::create_dir_all(record_dir)
+                .context("Failed to create SKYNET_RECORD_DIR")?;
+            let path = record_dir.join(format!("{fingerprint}.json"));
+            let json = serde_json::to_string_pretty(&recording)
+                .context("Failed to serialize recording")?;
+            std::fs::write(&path, json)
+                .with_context(|| format!("Failed to write recording to {}", path.display()))?;
+        }
 
-                if let Some(event) = line.strip_prefix("event: ") {
-                    event_type = Some(event.to_string());
-                } else if let Some(data) = line.strip_prefix("data: ") {
-                    match event_type.as_deref() {
-                        Some("finding") => {
-                            match serde_json::from_str::<SecurityFinding>(data) {
-                                Ok(finding) => on_finding(finding),
-                                Err(e) => debug!("Warning: Failed to parse finding: {e} (data: {data})"),
-                            }
-                        }
-                        Some("error") => {
-                            anyhow::bail!("Server error: {data}");
-                        }
-                        Some("complete") => {
-                            // Stream completed successfully
-                        }
-                        _ => {}
-                    }
-                }
-            }
+        Ok(())
+    }
+
+    /// Replay a previously recorded streaming call instead of hitting the
+    /// network, feeding its stored raw chunks through the same
+    /// `SseLineBuffer` live calls use, so partial-line buffering across
+    /// chunk boundaries is exercised identically.
+    fn replay_stream<F>(replay_dir: &std::path::Path, fingerprint: &str, mut on_finding: F) -> Result<()>
+    where
+        F: FnMut(SecurityFinding),
+    {
+        let path = replay_dir.join(format!("{fingerprint}.json"));
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("No recording found at {}", path.display()))?;
+        let recording: Recording =
+            serde_json::from_str(&json).context("Failed to parse recording")?;
+
+        if recording.status < 200 || recording.status >= 300 {
+            anyhow::bail!("API request failed with status {}", recording.status);
+        }
+
+        let mut parser = SseLineBuffer::new();
+        for chunk in &recording.chunks {
+            process_sse_chunk(&mut parser, chunk, &mut on_finding)?;
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sse_line_buffer_fires_once_per_finding() {
+        let mut parser = SseLineBuffer::new();
+        let mut events = Vec::new();
+
+        parser.feed(
+            "event: finding\ndata: {\"id\":\"1\"}\n\nevent: finding\ndata: {\"id\":\"2\"}\n\n",
+            |event, data| events.push((event.to_string(), data.to_string())),
+        );
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0], ("finding".to_string(), "{\"id\":\"1\"}".to_string()));
+        assert_eq!(events[1], ("finding".to_string(), "{\"id\":\"2\"}".to_string()));
+    }
+
+    #[test]
+    fn sse_line_buffer_handles_data_split_across_chunks() {
+        let mut parser = SseLineBuffer::new();
+        let mut events = Vec::new();
+
+        // The "data:" line is split mid-way through the JSON payload, as
+        // can happen when a finding straddles a network read boundary.
+        parser.feed("event: finding\ndata: {\"id\":\"1", |event, data| {
+            events.push((event.to_string(), data.to_string()))
+        });
+        parser.feed("\"}\n\n", |event, data| {
+            events.push((event.to_string(), data.to_string()))
+        });
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].1, "{\"id\":\"1\"}");
+    }
+
+    #[test]
+    fn sse_line_buffer_resets_event_type_on_blank_line() {
+        let mut parser = SseLineBuffer::new();
+        let mut events = Vec::new();
+
+        parser.feed("event: finding\n\ndata: orphaned\n\n", |event, data| {
+            events.push((event.to_string(), data.to_string()))
+        });
+
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_sensitive_to_input() {
+        let a = ApiClient::fingerprint_request("POST", "http://x/api", "{}");
+        let b = ApiClient::fingerprint_request("POST", "http://x/api", "{}");
+        let c = ApiClient::fingerprint_request("POST", "http://x/api", "{\"a\":1}");
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn recording_round_trips_through_json() {
+        let recording = Recording {
+            request_fingerprint: "abc123".to_string(),
+            status: 200,
+            chunks: vec!["event: finding\ndata: {\"id\":\"1\"}\n\n".to_string()],
+        };
+
+        let json = serde_json::to_string(&recording).unwrap();
+        let parsed: Recording = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.request_fingerprint, recording.request_fingerprint);
+        assert_eq!(parsed.chunks.len(), 1);
+    }
+
+    fn sample_finding_json() -> &'static str {
+        "{\"id\":\"1\",\"title\":\"t\",\"description\":\"d\",\"severityLevel\":2,\"filePath\":\"a.rs\",\"remediation\":\"r\"}"
+    }
+
+    #[tokio::test]
+    async fn analyze_stream_replays_a_finding_split_across_recorded_chunks() {
+        let replay_dir = std::env::temp_dir().join(format!(
+            "skynet_review_replay_test_{}_{}",
+            std::process::id(),
+            line!()
+        ));
+        std::fs::create_dir_all(&replay_dir).unwrap();
+
+        let client = ApiClient {
+            base_url: "http://example.invalid".to_string(),
+            client: Client::new(),
+            api_key: None,
+            record_dir: None,
+            replay_dir: Some(replay_dir.clone()),
+        };
+
+        let request = AnalysisRequest {
+            file_paths: vec!["a.rs".to_string()],
+            file_contents: std::collections::HashMap::new(),
+            repository_context: None,
+        };
+        let url = format!("{}/api/analyze/stream", client.base_url);
+        let body = serde_json::to_string(&request).unwrap();
+        let fingerprint = ApiClient::fingerprint_request("POST", &url, &body);
+
+        // The finding's JSON payload is split mid-way across two recorded
+        // chunks, exactly as a live network read might split it.
+        let data = sample_finding_json();
+        let split = data.len() / 2;
+        let recording = Recording {
+            request_fingerprint: fingerprint.clone(),
+            status: 200,
+            chunks: vec![
+                format!("event: finding\ndata: {}", &data[..split]),
+                format!("{}\n\n", &data[split..]),
+            ],
+        };
+        std::fs::write(
+            replay_dir.join(format!("{fingerprint}.json")),
+            serde_json::to_string(&recording).unwrap(),
+        )
+        .unwrap();
+
+        let mut found = Vec::new();
+        client
+            .analyze_stream(request, |finding| found.push(finding))
+            .await
+            .unwrap();
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "1");
+
+        std::fs::remove_dir_all(&replay_dir).ok();
+    }
+}
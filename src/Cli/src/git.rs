@@ -79,9 +79,17 @@ fn validate_path_within_repo(path: &std::path::Path, repo_root: &std::path::Path
     }
 }
 
-/// Get a list of changed files based on diff target
+/// Get a list of changed files based on diff target, using the current
+/// directory's repository.
 pub fn get_changed_files(target: &DiffTarget) -> Result<GitDiffResult> {
-    let repo_root = get_repository_root()?;
+    get_changed_files_in(&get_repository_root()?, target)
+}
+
+/// Get a list of changed files based on diff target, against an
+/// explicit repository root. Used by the webhook server to diff a
+/// specific configured checkout rather than the process's cwd.
+pub fn get_changed_files_in(repo_root: &std::path::Path, target: &DiffTarget) -> Result<GitDiffResult> {
+    let repo_root = repo_root.to_path_buf();
 
     // Build the git diff command based on target
     let (args, description) = match target {
@@ -128,6 +136,94 @@ pub fn get_changed_files(target: &DiffTarget) -> Result<GitDiffResult> {
     })
 }
 
+/// Attribution for a single line, as reported by `git blame`.
+#[derive(Debug, Clone)]
+pub struct BlameInfo {
+    pub short_sha: String,
+    pub author: String,
+    pub date: String,
+}
+
+/// Blame a single line of a file, returning who introduced it and when.
+pub fn blame_line(repo_root: &std::path::Path, file_path: &std::path::Path, line_number: u32) -> Result<BlameInfo> {
+    if !validate_path_within_repo(file_path, repo_root) {
+        anyhow::bail!("Refusing to blame a path outside the repository root");
+    }
+
+    let range = format!("{line_number},{line_number}");
+    let output = Command::new("git")
+        .args(["blame", "-L", &range, "--porcelain", &file_path.to_string_lossy()])
+        .current_dir(repo_root)
+        .output()
+        .context("Failed to execute git blame.")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Git blame failed. Verify the file and line number exist.");
+    }
+
+    let text = String::from_utf8(output.stdout).context("Failed to parse git blame output.")?;
+    let (commit, author) = parse_blame_porcelain(&text)?;
+    let short_sha = commit.chars().take(7).collect::<String>();
+    let date = commit_date(repo_root, &commit)?;
+
+    Ok(BlameInfo {
+        short_sha,
+        author,
+        date,
+    })
+}
+
+/// Parse the `--porcelain` header block emitted for a single blamed line
+/// into `(commit_sha, author_name)`.
+fn parse_blame_porcelain(text: &str) -> Result<(String, String)> {
+    let mut lines = text.lines();
+    let header = lines.next().context("Empty git blame output.")?;
+    let commit = header
+        .split_whitespace()
+        .next()
+        .context("Malformed git blame header.")?
+        .to_string();
+
+    let mut author = None;
+    for line in lines {
+        if let Some(rest) = line.strip_prefix("author ") {
+            author = Some(rest.to_string());
+        } else if line.starts_with('\t') {
+            break;
+        }
+    }
+
+    let author = author.context("Git blame output did not include an author.")?;
+    Ok((commit, author))
+}
+
+/// Look up the short, human-readable author date (`YYYY-MM-DD`) of commit `sha`.
+fn commit_date(repo_root: &std::path::Path, sha: &str) -> Result<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ad", "--date=short", sha])
+        .current_dir(repo_root)
+        .output()
+        .context("Failed to execute git log.")?;
+
+    if !output.status.success() {
+        anyhow::bail!("Git log failed while looking up commit date.");
+    }
+
+    Ok(String::from_utf8(output.stdout)
+        .context("Failed to parse git log output.")?
+        .trim()
+        .to_string())
+}
+
+/// Express `path` as a repo-relative, forward-slash-separated string, for
+/// keying analysis requests/findings by the same path GitHub's PR diff API
+/// and `git blame` use. Falls back to `path` as given if it isn't inside
+/// `repo_root` (e.g. already relative, or outside the repo entirely).
+pub fn repo_relative_path(repo_root: &std::path::Path, path: &std::path::Path) -> String {
+    let relative = path.strip_prefix(repo_root).unwrap_or(path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
 /// Filter files to only include analyzable source files
 /// If `extensions` is None, uses default set of source file extensions
 pub fn filter_analyzable_files(files: Vec<PathBuf>, extensions: Option<&[&str]>) -> Vec<PathBuf> {
@@ -149,3 +245,52 @@ pub fn filter_analyzable_files(files: Vec<PathBuf>, extensions: Option<&[&str]>)
         .filter(|path| path.exists())
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_blame_porcelain_extracts_commit_and_author() {
+        let text = "abc123def456 10 10 1\n\
+                     author Jane Doe\n\
+                     author-mail <jane@example.com>\n\
+                     author-time 1700000000\n\
+                     author-tz +0000\n\
+                     summary Fix the thing\n\
+                     \tlet x = 1;\n";
+
+        let (commit, author) = parse_blame_porcelain(text).unwrap();
+
+        assert_eq!(commit, "abc123def456");
+        assert_eq!(author, "Jane Doe");
+    }
+
+    #[test]
+    fn parse_blame_porcelain_rejects_missing_author() {
+        let text = "abc123def456 10 10 1\n\tlet x = 1;\n";
+
+        assert!(parse_blame_porcelain(text).is_err());
+    }
+
+    #[test]
+    fn parse_blame_porcelain_rejects_empty_output() {
+        assert!(parse_blame_porcelain("").is_err());
+    }
+
+    #[test]
+    fn repo_relative_path_strips_repo_root() {
+        let root = PathBuf::from("/repo");
+        let path = PathBuf::from("/repo/src/main.rs");
+
+        assert_eq!(repo_relative_path(&root, &path), "src/main.rs");
+    }
+
+    #[test]
+    fn repo_relative_path_falls_back_to_input_when_not_under_root() {
+        let root = PathBuf::from("/repo");
+        let path = PathBuf::from("src/main.rs");
+
+        assert_eq!(repo_relative_path(&root, &path), "src/main.rs");
+    }
+}
@@ -0,0 +1,289 @@
+use crate::models::{severity_name, SecurityFinding};
+use anyhow::{Context, Result};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+/// A single inline comment anchored to a line in a pull request diff.
+#[derive(Debug, Serialize)]
+struct ReviewComment {
+    path: String,
+    line: i32,
+    body: String,
+}
+
+/// Body of a GitHub "create a review" request.
+#[derive(Debug, Serialize)]
+struct ReviewRequest {
+    body: String,
+    event: String,
+    comments: Vec<ReviewComment>,
+}
+
+/// One entry of a GitHub "list pull request files" response. `patch` is
+/// the unified diff hunk text for the file, absent for binary files.
+#[derive(Debug, Deserialize)]
+struct PullRequestFile {
+    filename: String,
+    patch: Option<String>,
+}
+
+/// Thin client for publishing analysis findings as GitHub PR review comments.
+pub struct GithubClient {
+    base_url: String,
+    client: Client,
+    token: String,
+}
+
+impl GithubClient {
+    pub fn new(token: String) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .context("Failed to create GitHub HTTP client")?;
+
+        Ok(Self {
+            base_url: "https://api.github.com".to_string(),
+            client,
+            token,
+        })
+    }
+
+    /// Publish `findings` as a single review on `owner/repo`'s pull request
+    /// `pr_number`. Findings that land on a line outside the PR's diff
+    /// hunks are listed in the review summary instead of posted inline,
+    /// since GitHub rejects the entire review if any comment isn't
+    /// anchored to the diff.
+    pub async fn post_review(
+        &self,
+        repo: &str,
+        pr_number: u64,
+        findings: &[SecurityFinding],
+    ) -> Result<()> {
+        let (owner, name) = repo
+            .split_once('/')
+            .context("--repo must be in the form owner/name")?;
+
+        let diff_lines = self.fetch_diff_lines(owner, name, pr_number).await?;
+
+        let mut comments = Vec::new();
+        let mut out_of_hunk = Vec::new();
+
+        for finding in findings {
+            let in_hunk = finding.line_number.is_some_and(|line| {
+                diff_lines
+                    .get(&finding.file_path)
+                    .is_some_and(|lines| lines.contains(&line))
+            });
+
+            if in_hunk {
+                comments.push(ReviewComment {
+                    path: finding.file_path.clone(),
+                    line: finding.line_number.unwrap(),
+                    body: render_comment_body(finding),
+                });
+            } else {
+                out_of_hunk.push(finding);
+            }
+        }
+
+        let request = ReviewRequest {
+            body: render_summary(findings, &out_of_hunk),
+            event: "COMMENT".to_string(),
+            comments,
+        };
+
+        let url = format!("{}/repos/{owner}/{name}/pulls/{pr_number}/reviews", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", "skynet-review")
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to submit PR review (timeout or connection issue)")?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            anyhow::bail!("GitHub API request failed with status {status}");
+        }
+
+        Ok(())
+    }
+
+    /// Fetch the set of new-file line numbers that are part of `pr_number`'s
+    /// diff, per file path. Pages through the full file list - a PR with
+    /// more than one page of files would otherwise have its later files
+    /// silently treated as "not in the diff".
+    async fn fetch_diff_lines(
+        &self,
+        owner: &str,
+        name: &str,
+        pr_number: u64,
+    ) -> Result<HashMap<String, HashSet<i32>>> {
+        const PER_PAGE: usize = 100;
+        let mut diff_lines = HashMap::new();
+        let mut page = 1u32;
+
+        loop {
+            let url = format!(
+                "{}/repos/{owner}/{name}/pulls/{pr_number}/files?per_page={PER_PAGE}&page={page}",
+                self.base_url
+            );
+
+            let response = self
+                .client
+                .get(&url)
+                .header("Authorization", format!("Bearer {}", self.token))
+                .header("Accept", "application/vnd.github+json")
+                .header("User-Agent", "skynet-review")
+                .send()
+                .await
+                .context("Failed to fetch PR diff (timeout or connection issue)")?;
+
+            if !response.status().is_success() {
+                let status = response.status();
+                anyhow::bail!("GitHub API request failed with status {status}");
+            }
+
+            let files: Vec<PullRequestFile> = response
+                .json()
+                .await
+                .context("Failed to parse PR file list")?;
+
+            let fetched = files.len();
+            for file in files {
+                let lines = file
+                    .patch
+                    .as_deref()
+                    .map(diff_lines_in_patch)
+                    .unwrap_or_default();
+                diff_lines.insert(file.filename, lines);
+            }
+
+            if fetched < PER_PAGE {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(diff_lines)
+    }
+}
+
+/// Parse a unified diff hunk (as returned by GitHub's PR files API) into
+/// the set of new-file line numbers it touches (added or context lines;
+/// removed-only lines don't exist in the new file).
+fn diff_lines_in_patch(patch: &str) -> HashSet<i32> {
+    let mut lines = HashSet::new();
+    let mut new_line = 0i32;
+
+    for line in patch.lines() {
+        if let Some(new_line_start) = parse_hunk_header(line) {
+            new_line = new_line_start;
+            continue;
+        }
+
+        if line.starts_with('-') {
+            continue;
+        }
+
+        if line.starts_with('+') || line.starts_with(' ') {
+            lines.insert(new_line);
+            new_line += 1;
+        }
+    }
+
+    lines
+}
+
+/// Parse a `@@ -l,s +l,s @@` hunk header into the new file's starting
+/// line number.
+fn parse_hunk_header(line: &str) -> Option<i32> {
+    let rest = line.strip_prefix("@@ ")?;
+    let plus = rest.split_whitespace().find(|part| part.starts_with('+'))?;
+    let start = plus.trim_start_matches('+').split(',').next()?;
+    start.parse().ok()
+}
+
+fn render_comment_body(finding: &SecurityFinding) -> String {
+    format!(
+        "**{}** [{}]\n\n{}\n\n**Remediation:** {}",
+        finding.title,
+        severity_name(finding.severity_level),
+        finding.description,
+        finding.remediation
+    )
+}
+
+fn render_summary(findings: &[SecurityFinding], out_of_hunk: &[&SecurityFinding]) -> String {
+    if findings.is_empty() {
+        return "skynet-review found no issues.".to_string();
+    }
+
+    let mut counts = [0usize; 5];
+    for finding in findings {
+        let idx = (finding.severity_level as usize).min(4);
+        counts[idx] += 1;
+    }
+
+    let mut summary = format!("skynet-review found {} issue(s):\n", findings.len());
+    for (level, count) in counts.iter().enumerate() {
+        if *count > 0 {
+            summary.push_str(&format!("- {}: {}\n", severity_name(level as u8), count));
+        }
+    }
+
+    if !out_of_hunk.is_empty() {
+        summary.push_str(&format!(
+            "\n{} finding(s) are outside this PR's diff and were not posted inline:\n",
+            out_of_hunk.len()
+        ));
+        for finding in out_of_hunk {
+            let line = finding
+                .line_number
+                .map(|l| l.to_string())
+                .unwrap_or_else(|| "?".to_string());
+            summary.push_str(&format!(
+                "- {} ({}:{})\n",
+                finding.title, finding.file_path, line
+            ));
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_lines_in_patch_includes_added_and_context_not_removed() {
+        let patch = "@@ -1,3 +1,4 @@\n line one\n-line two\n+line two changed\n+line three\n line four\n";
+
+        let lines = diff_lines_in_patch(patch);
+
+        // New file: 1 (context), 2 (added "changed"), 3 (added "three"), 4 (context)
+        assert_eq!(lines, HashSet::from([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn diff_lines_in_patch_handles_multiple_hunks() {
+        let patch = "@@ -1,1 +1,1 @@\n-old\n+new\n@@ -10,1 +10,2 @@\n context\n+added\n";
+
+        let lines = diff_lines_in_patch(patch);
+
+        assert_eq!(lines, HashSet::from([1, 10, 11]));
+    }
+
+    #[test]
+    fn parse_hunk_header_extracts_new_file_start_line() {
+        assert_eq!(parse_hunk_header("@@ -5,3 +10,4 @@ fn foo() {"), Some(10));
+        assert_eq!(parse_hunk_header("not a header"), None);
+    }
+}
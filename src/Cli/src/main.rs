@@ -1,13 +1,21 @@
+use anyhow::Context;
 use clap::{Parser, Subcommand};
 use colored::*;
 use std::path::PathBuf;
 
 mod api_client;
 mod git;
+mod github;
+mod metrics;
 mod models;
+mod notifier;
 mod output;
+mod picker;
+mod server;
 
 use api_client::ApiClient;
+use github::GithubClient;
+use models::SecurityFinding;
 
 #[derive(Parser)]
 #[command(name = "skynet-review")]
@@ -48,10 +56,54 @@ enum Commands {
         /// Only include these file extensions (comma-separated)
         #[arg(long, value_delimiter = ',')]
         include_ext: Option<Vec<String>>,
+
+        /// Publish findings as inline comments on a GitHub pull request
+        #[arg(long)]
+        post_review: bool,
+
+        /// Repository to post the review to, as owner/name (requires --post-review)
+        #[arg(long, requires = "post_review")]
+        repo: Option<String>,
+
+        /// Pull request number to post the review to (requires --post-review)
+        #[arg(long, requires = "post_review")]
+        pr: Option<u64>,
+
+        /// Interactively select which changed files to analyze (requires --git-diff)
+        #[arg(long, requires = "git_diff")]
+        interactive: bool,
+
+        /// Email the analysis results to reviewers
+        #[arg(long)]
+        email: bool,
+
+        /// Recipient address (requires --email)
+        #[arg(long, requires = "email")]
+        email_to: Option<String>,
+
+        /// Sender address (requires --email)
+        #[arg(long, requires = "email")]
+        email_from: Option<String>,
+
+        /// Write Prometheus metrics for this run to a file
+        #[arg(long)]
+        metrics_file: Option<PathBuf>,
     },
 
     /// Check if services are healthy
     Health,
+
+    /// Run a webhook server that analyzes pushed diffs automatically
+    Serve {
+        /// Address to bind the webhook server to
+        #[arg(long, default_value = "0.0.0.0:8080")]
+        bind: String,
+
+        /// Path to a JSON config file listing each repo's webhook secret
+        /// and local checkout path
+        #[arg(long, default_value = "skynet-review.json")]
+        config: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -66,11 +118,30 @@ async fn main() -> anyhow::Result<()> {
             staged,
             commit,
             include_ext,
+            post_review,
+            repo,
+            pr,
+            interactive,
+            email,
+            email_to,
+            email_from,
+            metrics_file,
         } => {
+            let mut diff_description: Option<String> = None;
+            let mut candidate_count = files.len();
+
             // Determine which files to analyze
             let files_to_analyze = if git_diff {
                 // Git diff mode
-                get_git_diff_files(staged, commit, &include_ext)?
+                let (diff_files, description, changed_count) =
+                    get_git_diff_files(staged, commit, &include_ext)?;
+                diff_description = Some(description);
+                candidate_count = changed_count;
+                if interactive {
+                    picker::pick_files(diff_files)?
+                } else {
+                    diff_files
+                }
             } else if files.is_empty() {
                 eprintln!(
                     "{}",
@@ -84,6 +155,9 @@ async fn main() -> anyhow::Result<()> {
                 apply_extension_filter(files, &include_ext)
             };
 
+            let skipped = candidate_count.saturating_sub(files_to_analyze.len());
+            metrics::global().record_files_skipped(skipped as u64);
+
             if files_to_analyze.is_empty() {
                 println!("{}", "No files to analyze.".yellow());
                 return Ok(());
@@ -95,8 +169,30 @@ async fn main() -> anyhow::Result<()> {
             }
             println!();
 
-            match analyze_files(&client, files_to_analyze).await {
-                Ok(_) => Ok(()),
+            let started_at = std::time::Instant::now();
+            let result = analyze_files(&client, files_to_analyze).await;
+            metrics::global().record_total_duration(started_at.elapsed().as_secs_f64());
+
+            if let Some(path) = &metrics_file {
+                std::fs::write(path, metrics::global().render_prometheus())
+                    .with_context(|| format!("Failed to write metrics to {}", path.display()))?;
+            }
+
+            match result {
+                Ok(findings) => {
+                    if email {
+                        notifier::send_digest(
+                            &findings,
+                            diff_description.as_deref(),
+                            email_to,
+                            email_from,
+                        );
+                    }
+                    if post_review {
+                        post_review_comments(findings, repo, pr).await?;
+                    }
+                    Ok(())
+                }
                 Err(e) => {
                     eprintln!("{} {}", "Error:".red().bold(), e);
                     std::process::exit(1);
@@ -122,6 +218,11 @@ async fn main() -> anyhow::Result<()> {
                 }
             }
         }
+
+        Commands::Serve { bind, config } => {
+            let config = server::ServeConfig::load(&config)?;
+            server::run(&bind, cli.gateway_url.clone(), config).await
+        }
     }
 }
 
@@ -129,7 +230,7 @@ fn get_git_diff_files(
     staged: bool,
     commit: Option<String>,
     include_ext: &Option<Vec<String>>,
-) -> anyhow::Result<Vec<PathBuf>> {
+) -> anyhow::Result<(Vec<PathBuf>, String, usize)> {
     // Check we're in a git repo
     if !git::is_git_repository()? {
         anyhow::bail!("Not inside a git repository. Use file paths instead of --git-diff");
@@ -146,6 +247,7 @@ fn get_git_diff_files(
 
     // Get changed files
     let result = git::get_changed_files(&target)?;
+    let changed_count = result.changed_files.len();
 
     println!(
         "{} {} in {} ({} files)",
@@ -162,7 +264,7 @@ fn get_git_diff_files(
 
     let filtered = git::filter_analyzable_files(result.changed_files, ext_refs.as_deref());
 
-    Ok(filtered)
+    Ok((filtered, result.description, changed_count))
 }
 
 fn apply_extension_filter(files: Vec<PathBuf>, include_ext: &Option<Vec<String>>) -> Vec<PathBuf> {
@@ -175,23 +277,57 @@ fn apply_extension_filter(files: Vec<PathBuf>, include_ext: &Option<Vec<String>>
     }
 }
 
-async fn analyze_files(client: &ApiClient, files: Vec<PathBuf>) -> anyhow::Result<()> {
-    // Read file contents
+async fn post_review_comments(
+    findings: Vec<SecurityFinding>,
+    repo: Option<String>,
+    pr: Option<u64>,
+) -> anyhow::Result<()> {
+    let repo = repo.ok_or_else(|| anyhow::anyhow!("--post-review requires --repo owner/name"))?;
+    let pr = pr.ok_or_else(|| anyhow::anyhow!("--post-review requires --pr <number>"))?;
+    let token = std::env::var("GITHUB_TOKEN")
+        .map_err(|_| anyhow::anyhow!("GITHUB_TOKEN must be set to use --post-review"))?;
+
+    println!("{}", "Posting review to GitHub...".cyan().bold());
+    let github = GithubClient::new(token)?;
+    github.post_review(&repo, pr, &findings).await?;
+    println!("{} Review posted to {repo}#{pr}", "✓".green().bold());
+
+    Ok(())
+}
+
+async fn analyze_files(
+    client: &ApiClient,
+    files: Vec<PathBuf>,
+) -> anyhow::Result<Vec<SecurityFinding>> {
+    // Read file contents, keyed by their path relative to the repo root so
+    // findings can be matched back against GitHub's PR diff and `git blame`
+    let repo_root = git::get_repository_root().ok();
     let mut file_contents = std::collections::HashMap::new();
 
     for file_path in &files {
-        let content = std::fs::read_to_string(file_path)
-            .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", file_path.display(), e))?;
+        let content = match std::fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!(
+                    "{} Failed to read {}: {e}",
+                    "Warning:".yellow().bold(),
+                    file_path.display()
+                );
+                metrics::global().record_file_failed();
+                continue;
+            }
+        };
 
-        let file_name = file_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+        let file_key = match &repo_root {
+            Some(root) => git::repo_relative_path(root, file_path),
+            None => file_path.to_string_lossy().to_string(),
+        };
 
-        file_contents.insert(file_name, content);
+        file_contents.insert(file_key, content);
     }
 
+    metrics::global().record_files_analyzed(file_contents.len() as u64);
+
     // Create analysis request
     let file_paths: Vec<String> = file_contents.keys().cloned().collect();
     let request = models::AnalysisRequest {
@@ -205,25 +341,26 @@ async fn analyze_files(client: &ApiClient, files: Vec<PathBuf>) -> anyhow::Resul
     println!("{}", "Security Analysis (streaming)".green().bold());
     println!();
 
-    let mut finding_count = 0;
+    let mut findings = Vec::new();
     client
         .analyze_stream(request, |finding| {
-            finding_count += 1;
+            metrics::global().record_finding(finding.severity_level);
             output::display_finding_streaming(&finding);
+            findings.push(finding);
         })
         .await?;
 
-    if finding_count == 0 {
+    if findings.is_empty() {
         println!("  {}", "No issues found!".green());
     } else {
         println!(
             "\n{} Found {} issue(s)",
             "Summary:".cyan().bold(),
-            finding_count
+            findings.len()
         );
     }
 
-    Ok(())
+    Ok(findings)
 }
 
 async fn check_health(client: &ApiClient) -> anyhow::Result<models::HealthResponse> {
@@ -0,0 +1,93 @@
+use crate::models::severity_name;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+/// Process-wide metrics for analysis runs, exposed in Prometheus text
+/// exposition format via `--metrics-file` or the webhook server's
+/// `/metrics` endpoint.
+pub struct Metrics {
+    findings_by_severity: Mutex<HashMap<u8, u64>>,
+    total_duration_seconds: Mutex<Option<f64>>,
+    files_analyzed: AtomicU64,
+    files_skipped: AtomicU64,
+    files_failed: AtomicU64,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        Self {
+            findings_by_severity: Mutex::new(HashMap::new()),
+            total_duration_seconds: Mutex::new(None),
+            files_analyzed: AtomicU64::new(0),
+            files_skipped: AtomicU64::new(0),
+            files_failed: AtomicU64::new(0),
+        }
+    }
+
+    pub fn record_finding(&self, severity_level: u8) {
+        let mut counts = self.findings_by_severity.lock().unwrap();
+        *counts.entry(severity_level).or_insert(0) += 1;
+    }
+
+    pub fn record_files_analyzed(&self, count: u64) {
+        self.files_analyzed.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_files_skipped(&self, count: u64) {
+        self.files_skipped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub fn record_file_failed(&self) {
+        self.files_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn record_total_duration(&self, seconds: f64) {
+        *self.total_duration_seconds.lock().unwrap() = Some(seconds);
+    }
+
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        if let Some(seconds) = *self.total_duration_seconds.lock().unwrap() {
+            out.push_str("# HELP skynet_review_analysis_duration_seconds Wall-clock duration of an analysis run\n");
+            out.push_str("# TYPE skynet_review_analysis_duration_seconds gauge\n");
+            out.push_str(&format!("skynet_review_analysis_duration_seconds {seconds}\n"));
+        }
+
+        out.push_str("# HELP skynet_review_findings_total Findings observed, labeled by severity\n");
+        out.push_str("# TYPE skynet_review_findings_total counter\n");
+        let findings = self.findings_by_severity.lock().unwrap();
+        for (severity_level, count) in findings.iter() {
+            out.push_str(&format!(
+                "skynet_review_findings_total{{severity=\"{}\"}} {count}\n",
+                severity_name(*severity_level)
+            ));
+        }
+
+        out.push_str("# HELP skynet_review_files_total Files processed, labeled by outcome\n");
+        out.push_str("# TYPE skynet_review_files_total counter\n");
+        out.push_str(&format!(
+            "skynet_review_files_total{{status=\"analyzed\"}} {}\n",
+            self.files_analyzed.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "skynet_review_files_total{{status=\"skipped\"}} {}\n",
+            self.files_skipped.load(Ordering::Relaxed)
+        ));
+        out.push_str(&format!(
+            "skynet_review_files_total{{status=\"failed\"}} {}\n",
+            self.files_failed.load(Ordering::Relaxed)
+        ));
+
+        out
+    }
+}
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+/// The process-wide metrics instance, shared by the CLI's `--metrics-file`
+/// writer and the webhook server's `/metrics` endpoint.
+pub fn global() -> &'static Metrics {
+    METRICS.get_or_init(Metrics::new)
+}
@@ -55,4 +55,15 @@ pub struct SecurityFinding {
 pub struct HealthResponse {
     pub status: String,
     pub service: String,
+}
+
+/// Human-readable name for a `SecurityFinding.severity_level`.
+pub fn severity_name(level: u8) -> &'static str {
+    match level {
+        0 => "CRITICAL",
+        1 => "HIGH",
+        2 => "MEDIUM",
+        3 => "LOW",
+        _ => "INFO",
+    }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+use crate::models::{severity_name, SecurityFinding};
+use anyhow::{Context, Result};
+use colored::*;
+use lettre::message::{header::ContentType, MultiPart, SinglePart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{Message, SmtpTransport, Transport};
+use std::collections::BTreeMap;
+
+/// Escape text for safe interpolation into the HTML email body. Findings
+/// come from analyzed source that may be adversarial, so nothing from a
+/// `SecurityFinding` should reach `html` unescaped.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Render findings, grouped by file and severity, into a plain-text body
+/// and an equivalent HTML body.
+fn render_bodies(findings: &[SecurityFinding], diff_description: Option<&str>) -> (String, String) {
+    let mut counts = [0usize; 5];
+    for finding in findings {
+        counts[(finding.severity_level as usize).min(4)] += 1;
+    }
+
+    let mut by_file: BTreeMap<&str, Vec<&SecurityFinding>> = BTreeMap::new();
+    for finding in findings {
+        by_file.entry(&finding.file_path).or_default().push(finding);
+    }
+    for file_findings in by_file.values_mut() {
+        file_findings.sort_by_key(|finding| finding.severity_level);
+    }
+
+    let mut plain = String::new();
+    let mut html = String::from("<html><body>");
+
+    plain.push_str(&format!("skynet-review found {} issue(s)\n", findings.len()));
+    html.push_str(&format!("<h2>skynet-review found {} issue(s)</h2>", findings.len()));
+
+    if let Some(description) = diff_description {
+        plain.push_str(&format!("Diff: {description}\n"));
+        html.push_str(&format!("<p>Diff: {}</p>", escape_html(description)));
+    }
+
+    plain.push('\n');
+    html.push_str("<ul>");
+    for (level, count) in counts.iter().enumerate() {
+        if *count > 0 {
+            plain.push_str(&format!("  {}: {}\n", severity_name(level as u8), count));
+            html.push_str(&format!("<li>{}: {}</li>", severity_name(level as u8), count));
+        }
+    }
+    html.push_str("</ul>");
+
+    for (file_path, file_findings) in &by_file {
+        plain.push_str(&format!("\n{file_path}\n"));
+        html.push_str(&format!("<h3>{}</h3><ul>", escape_html(file_path)));
+
+        for finding in file_findings {
+            plain.push_str(&format!(
+                "  [{}] {} (line {})\n    {}\n    Remediation: {}\n",
+                severity_name(finding.severity_level),
+                finding.title,
+                finding.line_number.map(|l| l.to_string()).unwrap_or_else(|| "?".to_string()),
+                finding.description,
+                finding.remediation,
+            ));
+            html.push_str(&format!(
+                "<li><b>[{}] {}</b> (line {})<br>{}<br><i>Remediation:</i> {}</li>",
+                severity_name(finding.severity_level),
+                escape_html(&finding.title),
+                finding.line_number.map(|l| l.to_string()).unwrap_or_else(|| "?".to_string()),
+                escape_html(&finding.description),
+                escape_html(&finding.remediation),
+            ));
+        }
+        html.push_str("</ul>");
+    }
+    html.push_str("</body></html>");
+
+    (plain, html)
+}
+
+/// Send the findings digest by email. Delivery is best-effort: a failure
+/// is logged but never changes the analysis exit status.
+pub fn send_digest(
+    findings: &[SecurityFinding],
+    diff_description: Option<&str>,
+    to: Option<String>,
+    from: Option<String>,
+) {
+    match try_send_digest(findings, diff_description, to, from) {
+        Ok(()) => println!("{} Emailed results digest", "✓".green().bold()),
+        Err(e) => eprintln!("{} Failed to email results digest: {e}", "Warning:".yellow().bold()),
+    }
+}
+
+fn try_send_digest(
+    findings: &[SecurityFinding],
+    diff_description: Option<&str>,
+    to: Option<String>,
+    from: Option<String>,
+) -> Result<()> {
+    let to = to.context("--email requires --email-to")?;
+    let from = from.context("--email requires --email-from")?;
+    let smtp_host = std::env::var("SKYNET_SMTP_HOST").context("SKYNET_SMTP_HOST must be set to use --email")?;
+
+    let (plain_body, html_body) = render_bodies(findings, diff_description);
+
+    let email = Message::builder()
+        .from(from.parse().context("Invalid --email-from address")?)
+        .to(to.parse().context("Invalid --email-to address")?)
+        .subject(format!("skynet-review: {} issue(s) found", findings.len()))
+        .multipart(
+            MultiPart::alternative()
+                .singlepart(SinglePart::builder().header(ContentType::TEXT_PLAIN).body(plain_body))
+                .singlepart(SinglePart::builder().header(ContentType::TEXT_HTML).body(html_body)),
+        )
+        .context("Failed to build email message")?;
+
+    let mut transport = SmtpTransport::relay(&smtp_host).context("Invalid SMTP host")?;
+
+    if let (Ok(user), Ok(pass)) = (std::env::var("SKYNET_SMTP_USER"), std::env::var("SKYNET_SMTP_PASS")) {
+        transport = transport.credentials(Credentials::new(user, pass));
+    }
+
+    transport
+        .build()
+        .send(&email)
+        .context("Failed to send email")?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_bodies_escapes_html_in_finding_fields() {
+        let findings = vec![SecurityFinding {
+            id: "1".to_string(),
+            title: "<script>alert(1)</script>".to_string(),
+            description: "uses \"eval\" & friends".to_string(),
+            severity_level: 0,
+            file_path: "<evil>.rs".to_string(),
+            line_number: Some(1),
+            remediation: "don't <b>do</b> that".to_string(),
+            code_snippet: None,
+        }];
+
+        let (plain, html) = render_bodies(&findings, None);
+
+        assert!(plain.contains("<script>"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(html.contains("&amp;"));
+    }
+}
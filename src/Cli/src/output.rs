@@ -1,5 +1,21 @@
+use crate::git;
 use crate::models::{AnalysisResult, SecurityFinding};
 use colored::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static FINDING_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Reset the running count used to number findings as they arrive from
+/// `analyze_stream`. Call this once before a streaming analysis starts.
+pub fn reset_finding_counter() {
+    FINDING_COUNTER.store(0, Ordering::SeqCst);
+}
+
+/// Display a single finding as it streams in, numbered in arrival order.
+pub fn display_finding_streaming(finding: &SecurityFinding) {
+    let number = FINDING_COUNTER.fetch_add(1, Ordering::SeqCst) + 1;
+    display_finding(number, finding);
+}
 
 pub fn display_results(results: &[AnalysisResult]) {
     for result in results {
@@ -53,6 +69,13 @@ fn display_finding(number: usize, finding: &SecurityFinding) {
 
     if let Some(line) = finding.line_number {
         println!("     Line: {line}");
+
+        if let Some(blame) = lookup_blame(&finding.file_path, line) {
+            println!(
+                "     Introduced by {} in {} ({})",
+                blame.author, blame.short_sha, blame.date
+            );
+        }
     }
 
     println!("     {}", finding.description);
@@ -68,3 +91,29 @@ fn display_finding(number: usize, finding: &SecurityFinding) {
     println!("     {}", finding.remediation.green());
     println!();
 }
+
+/// Best-effort git blame lookup for a finding's line. Returns `None` if
+/// we're not in a repository or the file/line can't be blamed, so a
+/// missing history never hides the finding itself. `file_path` must be
+/// relative to the repository root (as `SecurityFinding.file_path` is,
+/// since the analysis request is keyed by repo-relative path) - a bare
+/// file name would resolve to a nonexistent path for any nested file.
+fn lookup_blame(file_path: &str, line_number: i32) -> Option<git::BlameInfo> {
+    if line_number < 0 {
+        return None;
+    }
+
+    let repo_root = git::get_repository_root().ok()?;
+    let path = repo_root.join(file_path);
+    git::blame_line(&repo_root, &path, line_number as u32).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_blame_returns_none_for_negative_line() {
+        assert!(lookup_blame("src/output.rs", -1).is_none());
+    }
+}
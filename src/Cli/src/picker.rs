@@ -0,0 +1,199 @@
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Score a candidate path against a fuzzy `query`. Every character of
+/// `query` must appear in `candidate` in order (a subsequence match);
+/// `None` means no match. Higher scores reward consecutive runs and
+/// matches at path-separator/word boundaries, and penalize the gap
+/// between matched characters.
+fn score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query: Vec<char> = query.chars().map(|c| c.to_ascii_lowercase()).collect();
+    let candidate: Vec<char> = candidate.chars().map(|c| c.to_ascii_lowercase()).collect();
+
+    let mut total = 0i64;
+    let mut search_from = 0usize;
+    let mut last_match: Option<usize> = None;
+
+    for &qc in &query {
+        let idx = (search_from..candidate.len()).find(|&i| candidate[i] == qc)?;
+
+        total += 1;
+        if let Some(last) = last_match {
+            if idx == last + 1 {
+                total += 15; // contiguity bonus
+            } else {
+                total -= (idx - last) as i64; // gap penalty
+            }
+        }
+
+        let at_boundary = idx == 0 || matches!(candidate[idx - 1], '/' | '_' | '-' | '.');
+        if at_boundary {
+            total += 10;
+        }
+
+        last_match = Some(idx);
+        search_from = idx + 1;
+    }
+
+    Some(total)
+}
+
+/// Rank `candidates` against `query`, dropping non-matches. Ties break by
+/// shorter path length.
+fn rank(query: &str, candidates: &[PathBuf]) -> Vec<PathBuf> {
+    let mut scored: Vec<(i64, usize, &PathBuf)> = candidates
+        .iter()
+        .filter_map(|path| {
+            let text = path.to_string_lossy();
+            score(query, &text).map(|s| (s, text.len(), path))
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.into_iter().map(|(_, _, path)| path.clone()).collect()
+}
+
+/// Drop into a fuzzy-search terminal picker over `candidates`, letting the
+/// user narrow the list by typing and toggle selections with Space.
+/// Returns the selected files; if the user confirms without selecting
+/// anything, returns every candidate matching the current query, in
+/// ranked order.
+pub fn pick_files(candidates: Vec<PathBuf>) -> anyhow::Result<Vec<PathBuf>> {
+    enable_raw_mode()?;
+    let result = run_picker(candidates);
+    disable_raw_mode()?;
+    result
+}
+
+fn run_picker(candidates: Vec<PathBuf>) -> anyhow::Result<Vec<PathBuf>> {
+    let mut query = String::new();
+    let mut selected: HashSet<PathBuf> = HashSet::new();
+    let mut cursor = 0usize;
+
+    loop {
+        let ranked = rank(&query, &candidates);
+        if cursor >= ranked.len() {
+            cursor = ranked.len().saturating_sub(1);
+        }
+        render(&query, &ranked, &selected, cursor);
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Esc => return Ok(Vec::new()),
+                KeyCode::Enter => {
+                    return Ok(if selected.is_empty() {
+                        ranked
+                    } else {
+                        candidates
+                            .into_iter()
+                            .filter(|path| selected.contains(path))
+                            .collect()
+                    });
+                }
+                KeyCode::Char(' ') | KeyCode::Tab => {
+                    if let Some(path) = ranked.get(cursor) {
+                        if !selected.remove(path) {
+                            selected.insert(path.clone());
+                        }
+                    }
+                }
+                KeyCode::Up => cursor = cursor.saturating_sub(1),
+                KeyCode::Down => {
+                    if cursor + 1 < ranked.len() {
+                        cursor += 1;
+                    }
+                }
+                KeyCode::Backspace => {
+                    query.pop();
+                    cursor = 0;
+                }
+                KeyCode::Char(c) => {
+                    query.push(c);
+                    cursor = 0;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn render(query: &str, ranked: &[PathBuf], selected: &HashSet<PathBuf>, cursor: usize) {
+    print!("\x1B[2J\x1B[H");
+    println!("Filter: {query}");
+    println!("(type to search, space to toggle, enter to confirm, esc to cancel)\n");
+
+    for (i, path) in ranked.iter().enumerate() {
+        let pointer = if i == cursor { ">" } else { " " };
+        let checkbox = if selected.contains(path) { "[x]" } else { "[ ]" };
+        println!("{pointer} {checkbox} {}", path.display());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score_empty_query_matches_everything_at_zero() {
+        assert_eq!(score("", "src/main.rs"), Some(0));
+    }
+
+    #[test]
+    fn score_rejects_non_subsequence() {
+        assert_eq!(score("xyz", "src/main.rs"), None);
+    }
+
+    #[test]
+    fn score_rewards_contiguous_runs() {
+        let contiguous = score("main", "src/main.rs").unwrap();
+        let scattered = score("mrs", "src/main.rs").unwrap();
+        assert!(contiguous > scattered);
+    }
+
+    #[test]
+    fn score_rewards_boundary_matches() {
+        let at_boundary = score("m", "src/main.rs").unwrap();
+        let mid_word = score("a", "src/main.rs").unwrap();
+        assert!(at_boundary > mid_word);
+    }
+
+    #[test]
+    fn score_penalizes_gaps() {
+        // Neither second match is contiguous or at a boundary, so only the
+        // gap differs: 'a' is 5 past 's', 'n' is 7 past it.
+        let small_gap = score("sa", "src/main.rs").unwrap();
+        let big_gap = score("sn", "src/main.rs").unwrap();
+        assert!(small_gap > big_gap);
+    }
+
+    #[test]
+    fn rank_drops_non_matches_and_orders_by_score() {
+        let candidates = vec![
+            PathBuf::from("src/main.rs"),
+            PathBuf::from("src/models.rs"),
+            PathBuf::from("README.md"),
+        ];
+
+        let ranked = rank("main", &candidates);
+
+        assert_eq!(ranked, vec![PathBuf::from("src/main.rs")]);
+    }
+
+    #[test]
+    fn rank_breaks_ties_by_shorter_path() {
+        let candidates = vec![PathBuf::from("aa/b.rs"), PathBuf::from("a/b.rs")];
+
+        let ranked = rank("b.rs", &candidates);
+
+        assert_eq!(ranked[0], PathBuf::from("a/b.rs"));
+    }
+}
@@ -0,0 +1,259 @@
+use crate::api_client::ApiClient;
+use crate::git;
+use crate::metrics;
+use crate::models;
+use anyhow::{Context, Result};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// A single repository this server is willing to analyze pushes for: its
+/// GitHub `owner/name`, the webhook secret it was configured with, and
+/// the local checkout to diff against.
+#[derive(Debug, Deserialize)]
+pub struct RepoConfig {
+    pub name: String,
+    pub secret: String,
+    pub path: PathBuf,
+}
+
+/// Webhook server configuration. Each configured repo carries its own
+/// secret and local checkout path, so one server can front several repos.
+#[derive(Debug, Deserialize)]
+pub struct ServeConfig {
+    pub repos: Vec<RepoConfig>,
+}
+
+impl ServeConfig {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read webhook config at {}", path.display()))?;
+        serde_json::from_str(&contents)
+            .with_context(|| format!("Failed to parse webhook config at {}", path.display()))
+    }
+}
+
+struct ServerState {
+    config: ServeConfig,
+    gateway_url: String,
+}
+
+/// Minimal shape of a GitHub `push` event payload: the pushed commit
+/// range, and which configured repo it belongs to. `process_push` diffs
+/// `before` against the local checkout's HEAD; `after` is only passed on
+/// as repository context.
+#[derive(Debug, Deserialize)]
+struct PushEvent {
+    before: String,
+    after: String,
+    repository: RepositoryInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryInfo {
+    full_name: String,
+}
+
+/// Run the webhook server, binding to `bind_addr` and forwarding analyzable
+/// changed files from verified push events to the gateway at `gateway_url`.
+pub async fn run(bind_addr: &str, gateway_url: String, config: ServeConfig) -> Result<()> {
+    let state = Arc::new(ServerState {
+        config,
+        gateway_url,
+    });
+
+    let app = Router::new()
+        .route("/webhook", post(handle_webhook))
+        .route("/metrics", get(handle_metrics))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(bind_addr)
+        .await
+        .with_context(|| format!("Failed to bind {bind_addr}"))?;
+
+    println!("Listening for webhooks on {bind_addr}");
+    axum::serve(listener, app)
+        .await
+        .context("Webhook server error")?;
+
+    Ok(())
+}
+
+async fn handle_metrics() -> String {
+    metrics::global().render_prometheus()
+}
+
+async fn handle_webhook(
+    State(state): State<Arc<ServerState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> StatusCode {
+    let Some(signature_header) = headers
+        .get("X-Hub-Signature-256")
+        .and_then(|v| v.to_str().ok())
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    let Some(hex_signature) = signature_header.strip_prefix("sha256=") else {
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    let Some(expected) = decode_hex(hex_signature) else {
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    let event: PushEvent = match serde_json::from_slice(&body) {
+        Ok(event) => event,
+        Err(_) => return StatusCode::BAD_REQUEST,
+    };
+
+    // Require the signature to verify under the specific repo the payload
+    // claims to be from, not merely under *some* configured repo's secret
+    // - otherwise one repo's secret holder could forge pushes for another.
+    let Some(repo) = state
+        .config
+        .repos
+        .iter()
+        .find(|repo| repo.name == event.repository.full_name)
+        .filter(|repo| verify_signature(&repo.secret, &body, &expected))
+    else {
+        return StatusCode::UNAUTHORIZED;
+    };
+
+    match process_push(&state.gateway_url, &repo.path, &event).await {
+        Ok(_) => StatusCode::OK,
+        Err(e) => {
+            eprintln!("Error processing webhook: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+/// Verify `expected` is the HMAC-SHA256 of `body` under `secret`, in
+/// constant time.
+fn verify_signature(secret: &str, body: &[u8], expected: &[u8]) -> bool {
+    let Ok(mut mac) = HmacSha256::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(body);
+    mac.verify_slice(expected).is_ok()
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+async fn process_push(gateway_url: &str, repo_root: &Path, event: &PushEvent) -> Result<()> {
+    let target = git::DiffTarget::Commit(event.before.clone());
+    let result = git::get_changed_files_in(repo_root, &target)?;
+    let changed_count = result.changed_files.len();
+    let files = git::filter_analyzable_files(result.changed_files, None);
+
+    metrics::global().record_files_skipped((changed_count - files.len()) as u64);
+
+    if files.is_empty() {
+        return Ok(());
+    }
+
+    metrics::global().record_files_analyzed(files.len() as u64);
+
+    let mut file_contents = HashMap::new();
+    for file_path in &files {
+        let content = std::fs::read_to_string(file_path)
+            .with_context(|| format!("Failed to read {}", file_path.display()))?;
+        let file_key = git::repo_relative_path(repo_root, file_path);
+        file_contents.insert(file_key, content);
+    }
+
+    let file_paths: Vec<String> = file_contents.keys().cloned().collect();
+    let request = models::AnalysisRequest {
+        file_paths,
+        file_contents,
+        repository_context: Some(format!(
+            "{} push {} -> {}",
+            event.repository.full_name, event.before, event.after
+        )),
+    };
+
+    let client = ApiClient::new(gateway_url)?;
+    client
+        .analyze_stream(request, |finding| {
+            metrics::global().record_finding(finding.severity_level);
+            println!(
+                "[severity {}] {} ({}:{})",
+                finding.severity_level,
+                finding.title,
+                finding.file_path,
+                finding.line_number.unwrap_or(0)
+            );
+        })
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_hex_round_trips_valid_input() {
+        assert_eq!(decode_hex("0a1b"), Some(vec![0x0a, 0x1b]));
+        assert_eq!(decode_hex(""), Some(vec![]));
+    }
+
+    #[test]
+    fn decode_hex_rejects_odd_length_and_non_hex() {
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+    }
+
+    #[test]
+    fn verify_signature_accepts_matching_mac() {
+        let secret = "s3cr3t";
+        let body = b"hello world";
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let expected = mac.finalize().into_bytes().to_vec();
+
+        assert!(verify_signature(secret, body, &expected));
+    }
+
+    #[test]
+    fn verify_signature_rejects_wrong_secret_or_tampered_body() {
+        let secret = "s3cr3t";
+        let body = b"hello world";
+
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(body);
+        let expected = mac.finalize().into_bytes().to_vec();
+
+        assert!(!verify_signature("wrong-secret", body, &expected));
+        assert!(!verify_signature(secret, b"tampered body", &expected));
+    }
+
+    #[test]
+    fn verify_signature_rejects_malformed_expected_length() {
+        assert!(!verify_signature("s3cr3t", b"body", &[1, 2, 3]));
+    }
+}